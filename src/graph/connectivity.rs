@@ -1,5 +1,7 @@
 //! Graph connectivity structures.
 
+use std::collections::{HashSet, VecDeque};
+
 use super::graph::{DirectedGraph, UndirectedGraph};
 
 /// Helper struct that carries data needed for the depth-first searches in
@@ -121,6 +123,209 @@ impl<'a> ConnectivityDirectedGraph<'a> {
             .collect()
     }
 
+    /// Collapses every strongly connected component found in
+    /// [`ConnectivityDirectedGraph::new`] into a single super-node and returns
+    /// the quotient DAG. The new graph has `self.num_cc` vertices (SCC ids are
+    /// shifted down to the 0-based range `0..num_cc`); an edge is added between
+    /// two SCCs whenever some original edge crosses from one to the other,
+    /// deduplicated so parallel crossings collapse to one arc.
+    ///
+    /// The result is acyclic, so it pairs naturally with `topological_sort` and
+    /// lets callers run DAG algorithms (longest path, DP over SCCs) on it.
+    pub fn condensation(&self) -> DirectedGraph {
+        let mut dag = DirectedGraph::new(self.num_cc, self.graph.num_e());
+        let mut seen = HashSet::new();
+        for u in 0..self.graph.num_v() {
+            for (_, v) in self.graph.adj_list(u) {
+                let (cu, cv) = (self.cc[u] - 1, self.cc[*v] - 1);
+                if cu != cv && seen.insert((cu, cv)) {
+                    dag.add_edge(cu, cv);
+                }
+            }
+        }
+        dag
+    }
+
+    /// Enumerates every simple path from `from` to `to` whose node count lies in
+    /// `[min_nodes, max_nodes]`, as an iterator yielding the vertices of each
+    /// path in order. Handy for 2-SAT/implication-graph debugging, where the
+    /// concrete dependency chains between two literals are what you want to see.
+    ///
+    /// The search is an explicit DFS that keeps the vertices currently on the
+    /// path in a `visited` set and a cursor per path vertex into its neighbor
+    /// list; it extends to unvisited neighbors while the path is shorter than
+    /// `max_nodes` and backtracks by popping both stacks.
+    pub fn all_simple_paths(
+        &self,
+        from: usize,
+        to: usize,
+        min_nodes: usize,
+        max_nodes: usize,
+    ) -> AllSimplePaths {
+        let n = self.graph.num_v();
+        let mut adj = vec![Vec::new(); n];
+        for u in 0..n {
+            for (_, v) in self.graph.adj_list(u) {
+                adj[u].push(*v);
+            }
+        }
+        let mut visited = vec![false; n];
+        visited[from] = true;
+        AllSimplePaths {
+            adj,
+            to,
+            min_nodes,
+            max_nodes,
+            visited,
+            path: vec![from],
+            cursor: vec![0],
+        }
+    }
+
+    /// Computes a (heuristically minimized) feedback arc set of `self.graph`:
+    /// the edge ids whose removal makes the graph acyclic, returned so callers
+    /// with a cyclic graph can reduce it to a DAG before running
+    /// `topological_sort`/`two_sat_assign`, which assume acyclicity.
+    ///
+    /// Uses the Eades–Lin–Smyth heuristic: repeatedly peel sinks onto the back
+    /// of a vertex sequence and sources onto the front; when neither exists,
+    /// pull the vertex maximizing `outdeg - indeg` to the front. Each round
+    /// rescans every vertex, so this takes O(V² + E) time rather than linear
+    /// time. The feedback arc set is exactly the edges that point backward in
+    /// the resulting ordering, together with every self-loop.
+    pub fn greedy_feedback_arc_set(&self) -> Vec<usize> {
+        let n = self.graph.num_v();
+        let mut succ = vec![Vec::new(); n];
+        let mut pred = vec![Vec::new(); n];
+        let mut out_deg = vec![0usize; n];
+        let mut in_deg = vec![0usize; n];
+        for u in 0..n {
+            for (_, v) in self.graph.adj_list(u) {
+                succ[u].push(*v);
+                pred[*v].push(u);
+                out_deg[u] += 1;
+                in_deg[*v] += 1;
+            }
+        }
+
+        let mut removed = vec![false; n];
+        let mut remaining = n;
+        // `left` grows from the front of the sequence, `right` from the back.
+        let mut left = Vec::new();
+        let mut right = VecDeque::new();
+
+        let remove = |u: usize,
+                      removed: &mut [bool],
+                          out_deg: &mut [usize],
+                          in_deg: &mut [usize]| {
+            removed[u] = true;
+            for &v in &succ[u] {
+                if !removed[v] {
+                    in_deg[v] -= 1;
+                }
+            }
+            for &p in &pred[u] {
+                if !removed[p] {
+                    out_deg[p] -= 1;
+                }
+            }
+        };
+
+        while remaining > 0 {
+            let mut peeled = true;
+            while peeled {
+                peeled = false;
+                for u in 0..n {
+                    if !removed[u] && out_deg[u] == 0 {
+                        remove(u, &mut removed, &mut out_deg, &mut in_deg);
+                        right.push_front(u);
+                        remaining -= 1;
+                        peeled = true;
+                    }
+                }
+                for u in 0..n {
+                    if !removed[u] && in_deg[u] == 0 {
+                        remove(u, &mut removed, &mut out_deg, &mut in_deg);
+                        left.push(u);
+                        remaining -= 1;
+                        peeled = true;
+                    }
+                }
+            }
+            if remaining == 0 {
+                break;
+            }
+            // Neither a source nor a sink remains: take the vertex with the
+            // greatest out-degree advantage and push it to the front.
+            let best = (0..n)
+                .filter(|&u| !removed[u])
+                .max_by_key(|&u| out_deg[u] as isize - in_deg[u] as isize)
+                .unwrap();
+            remove(best, &mut removed, &mut out_deg, &mut in_deg);
+            left.push(best);
+            remaining -= 1;
+        }
+
+        let mut pos = vec![0usize; n];
+        for (i, &u) in left.iter().chain(right.iter()).enumerate() {
+            pos[u] = i;
+        }
+
+        let mut feedback = Vec::new();
+        for u in 0..n {
+            for (e, v) in self.graph.adj_list(u) {
+                if pos[u] >= pos[*v] {
+                    feedback.push(*e);
+                }
+            }
+        }
+        feedback
+    }
+
+    /// Returns the transitive reduction of `self.graph`, assumed acyclic (for
+    /// instance the output of [`ConnectivityDirectedGraph::condensation`], or
+    /// any DAG whose acyclicity was checked with `topological_sort`): the graph
+    /// with the fewest edges that preserves the same reachability.
+    ///
+    /// Vertices are processed in reverse topological order while a reachability
+    /// bitset of each one's descendants is maintained. An edge `(u, v)` is kept
+    /// only when `v` is not already reachable through some other direct
+    /// successor of `u`.
+    pub fn transitive_reduction(&self) -> DirectedGraph {
+        let n = self.graph.num_v();
+        let order = self.topological_sort();
+
+        // Descendants reachable from each vertex (excluding itself).
+        let mut reach = vec![vec![false; n]; n];
+        let mut reduced = DirectedGraph::new(n, self.graph.num_e());
+
+        for &u in order.iter().rev() {
+            // Union of the descendant sets of u's direct successors: a successor
+            // v reachable through another successor makes the edge u -> v
+            // redundant.
+            let mut through = vec![false; n];
+            for (_, v) in self.graph.adj_list(u) {
+                for w in 0..n {
+                    through[w] |= reach[*v][w];
+                }
+            }
+            for (_, v) in self.graph.adj_list(u) {
+                if !through[*v] {
+                    // Marking v keeps only one of several parallel u -> v edges.
+                    through[*v] = true;
+                    reduced.add_edge(u, *v);
+                }
+            }
+            // reach[u] = successors ∪ their descendants.
+            reach[u] = through;
+            for (_, v) in self.graph.adj_list(u) {
+                reach[u][*v] = true;
+            }
+        }
+
+        reduced
+    }
+
     /// Gets the vertices of a graph according to a topological order of the
     /// strongly connected components. Most often used on DAGs.
     pub fn topological_sort(&self) -> Vec<usize> {
@@ -130,6 +335,206 @@ impl<'a> ConnectivityDirectedGraph<'a> {
     }
 }
 
+/// Immediate-dominator tree of a directed graph rooted at a fixed source,
+/// computed with the iterative Cooper–Harvey–Kennedy algorithm.
+///
+/// A vertex `d` dominates `v` if every path from the root to `v` passes through
+/// `d`; the immediate dominator is the closest such `d` other than `v` itself.
+/// Vertices not reachable from the root have no dominator.
+pub struct Dominators {
+    root: usize,
+    /// Postorder number of each vertex in the DFS from the root, or
+    /// `usize::MAX` for unreachable vertices.
+    post: Vec<usize>,
+    /// Immediate dominator of each vertex, `None` when unreachable. `idom[root]`
+    /// is `root` itself.
+    idom: Vec<Option<usize>>,
+}
+
+impl Dominators {
+    /// Builds the dominator tree of `graph` rooted at `root`.
+    pub fn new(graph: &DirectedGraph, root: usize) -> Self {
+        let n = graph.num_v();
+        let mut succ = vec![Vec::new(); n];
+        let mut pred = vec![Vec::new(); n];
+        for u in 0..n {
+            for (_, v) in graph.adj_list(u) {
+                succ[u].push(*v);
+                pred[*v].push(u);
+            }
+        }
+
+        // DFS from the root to assign postorder numbers.
+        let mut post = vec![usize::MAX; n];
+        let mut order = Vec::new();
+        let mut visited = vec![false; n];
+        Self::dfs(root, &succ, &mut visited, &mut post, &mut order);
+
+        let mut idom = vec![None; n];
+        idom[root] = Some(root);
+        // Process the reachable vertices in reverse postorder until no change.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &b in order.iter().rev() {
+                if b == root {
+                    continue;
+                }
+                let mut new_idom = None;
+                for &p in &pred[b] {
+                    if idom[p].is_some() {
+                        new_idom = Some(match new_idom {
+                            None => p,
+                            Some(ni) => Self::intersect(p, ni, &post, &idom),
+                        });
+                    }
+                }
+                if idom[b] != new_idom {
+                    idom[b] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        Self { root, post, idom }
+    }
+
+    fn dfs(
+        u: usize,
+        succ: &[Vec<usize>],
+        visited: &mut [bool],
+        post: &mut [usize],
+        order: &mut Vec<usize>,
+    ) {
+        visited[u] = true;
+        for &v in &succ[u] {
+            if !visited[v] {
+                Self::dfs(v, succ, visited, post, order);
+            }
+        }
+        post[u] = order.len();
+        order.push(u);
+    }
+
+    /// Walks the two idom-chain fingers up until they meet, always advancing
+    /// whichever finger has the lower postorder number.
+    fn intersect(mut a: usize, mut b: usize, post: &[usize], idom: &[Option<usize>]) -> usize {
+        while a != b {
+            while post[a] < post[b] {
+                a = idom[a].unwrap();
+            }
+            while post[b] < post[a] {
+                b = idom[b].unwrap();
+            }
+        }
+        a
+    }
+
+    /// The immediate dominator of `v`, or `None` if `v` is the root or is not
+    /// reachable from the root.
+    pub fn immediate_dominator(&self, v: usize) -> Option<usize> {
+        if v == self.root || self.post[v] == usize::MAX {
+            None
+        } else {
+            self.idom[v]
+        }
+    }
+
+    /// Iterates the dominators of `v`, starting with `v` and following the idom
+    /// chain up to and including the root. Returns `None` when `v` is
+    /// unreachable.
+    pub fn dominators(&self, v: usize) -> Option<DominatorsChain<'_>> {
+        if self.post[v] == usize::MAX {
+            None
+        } else {
+            Some(DominatorsChain {
+                dom: self,
+                node: Some(v),
+            })
+        }
+    }
+
+    /// Like [`Dominators::dominators`] but omits `v` itself.
+    pub fn strict_dominators(&self, v: usize) -> Option<DominatorsChain<'_>> {
+        self.dominators(v).map(|mut chain| {
+            chain.next();
+            chain
+        })
+    }
+}
+
+/// Iterator over the idom chain produced by [`Dominators::dominators`].
+pub struct DominatorsChain<'a> {
+    dom: &'a Dominators,
+    node: Option<usize>,
+}
+
+impl Iterator for DominatorsChain<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let node = self.node?;
+        self.node = if node == self.dom.root {
+            None
+        } else {
+            self.dom.idom[node]
+        };
+        Some(node)
+    }
+}
+
+/// Iterator over the simple paths enumerated by
+/// [`ConnectivityDirectedGraph::all_simple_paths`].
+pub struct AllSimplePaths {
+    adj: Vec<Vec<usize>>,
+    to: usize,
+    min_nodes: usize,
+    max_nodes: usize,
+    /// Whether each vertex currently sits on the path.
+    visited: Vec<bool>,
+    /// Vertices on the path, from `from` to the current frontier.
+    path: Vec<usize>,
+    /// Cursor into `adj[path[i]]` for the next neighbor to try.
+    cursor: Vec<usize>,
+}
+
+impl Iterator for AllSimplePaths {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        while let Some(&u) = self.path.last() {
+            let mut descended = false;
+            if self.path.len() < self.max_nodes {
+                let mut ci = *self.cursor.last().unwrap();
+                while ci < self.adj[u].len() {
+                    let v = self.adj[u][ci];
+                    ci += 1;
+                    if !self.visited[v] {
+                        *self.cursor.last_mut().unwrap() = ci;
+                        self.visited[v] = true;
+                        self.path.push(v);
+                        self.cursor.push(0);
+                        if v == self.to && self.path.len() >= self.min_nodes {
+                            return Some(self.path.clone());
+                        }
+                        descended = true;
+                        break;
+                    }
+                }
+                if !descended {
+                    *self.cursor.last_mut().unwrap() = ci;
+                }
+            }
+            if !descended {
+                let last = self.path.pop().unwrap();
+                self.cursor.pop();
+                self.visited[last] = false;
+            }
+        }
+        None
+    }
+}
+
 pub struct ConnectivityUndirectedGraph<'a> {
     /// Immutable graph, frozen for the lifetime of the ConnectivityGraph object.
     pub graph: &'a UndirectedGraph,
@@ -145,6 +550,21 @@ pub struct ConnectivityUndirectedGraph<'a> {
     pub num_vcc: usize,
 }
 
+/// Block-cut forest produced by [`ConnectivityUndirectedGraph::block_cut_tree`],
+/// bundling the tree with the metadata needed to translate between original
+/// vertices/blocks and tree-node ids.
+pub struct BlockCutTree {
+    /// The block-cut forest itself.
+    pub tree: UndirectedGraph,
+    /// For each tree node, whether it represents an articulation vertex (true)
+    /// or a 2VCC block (false).
+    pub is_articulation: Vec<bool>,
+    /// Tree node of block `b`, indexed by the 0-based block id `vcc - 1`.
+    pub block_node: Vec<usize>,
+    /// Tree node of each original vertex if it is an articulation point.
+    pub ap_node: Vec<Option<usize>>,
+}
+
 impl<'a> ConnectivityUndirectedGraph<'a> {
     /// Computes CCs (connected components), SCCs (strongly connected
     /// components), 2ECCs (2-edge-connected components), and/or 2VCCs
@@ -231,6 +651,58 @@ impl<'a> ConnectivityUndirectedGraph<'a> {
         }
     }
 
+    /// Builds the block-cut forest of the biconnected decomposition computed in
+    /// [`ConnectivityUndirectedGraph::new`].
+    ///
+    /// The tree has one node per 2VCC block and one node per articulation
+    /// vertex; every articulation vertex is joined to each block it takes part
+    /// in. Rooting the result gives callers a tree on which LCA/path queries
+    /// answer "which biconnected components must a path between u and v cross".
+    ///
+    /// Block `b` (for `vcc` id `b + 1`) maps to tree node `block_node[b]`, an
+    /// articulation vertex `u` maps to `ap_node[u]`, and `is_articulation[t]`
+    /// distinguishes the two kinds of tree node.
+    pub fn block_cut_tree(&self) -> BlockCutTree {
+        let num_blocks = self.num_vcc;
+        let block_node = (0..num_blocks).collect::<Vec<_>>();
+
+        // Articulation vertices take the tree-node ids after the blocks.
+        let mut ap_node = vec![None; self.graph.num_v()];
+        let mut next = num_blocks;
+        for u in 0..self.graph.num_v() {
+            if self.isAP[u] {
+                ap_node[u] = Some(next);
+                next += 1;
+            }
+        }
+        let num_nodes = next;
+
+        let mut is_articulation = vec![false; num_nodes];
+        for node in ap_node.iter().flatten() {
+            is_articulation[*node] = true;
+        }
+
+        let mut tree = UndirectedGraph::new(num_nodes, 2 * num_nodes);
+        for u in 0..self.graph.num_v() {
+            if let Some(anode) = ap_node[u] {
+                let mut blocks = HashSet::new();
+                for (e, _) in self.graph.adj_list(u) {
+                    let b = self.vcc[*e];
+                    if b != 0 && blocks.insert(b) {
+                        tree.add_edge(anode, block_node[b - 1]);
+                    }
+                }
+            }
+        }
+
+        BlockCutTree {
+            tree,
+            is_articulation,
+            block_node,
+            ap_node,
+        }
+    }
+
     /// In an undirected graph, determines whether u is an articulation vertex.
     pub fn is_cut_vertex(&self, u: usize) -> bool {
         //return self.isAP[u];
@@ -268,6 +740,59 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_transitive_reduction() {
+        // a -> b, b -> c, a -> c: the direct a -> c edge is redundant.
+        let mut graph = DirectedGraph::new(3, 3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(0, 2);
+
+        let reduced = ConnectivityDirectedGraph::new(&graph).transitive_reduction();
+        assert_eq!(reduced.num_e(), 2);
+    }
+
+    #[test]
+    fn test_all_simple_paths() {
+        // Diamond: 0 -> 1 -> 3 and 0 -> 2 -> 3.
+        let mut graph = DirectedGraph::new(4, 4);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+
+        let mut paths = ConnectivityDirectedGraph::new(&graph)
+            .all_simple_paths(0, 3, 0, 4)
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn test_condensation() {
+        // A 2-cycle (0 <-> 1) with one outgoing edge 1 -> 2.
+        let mut graph = DirectedGraph::new(3, 4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 0);
+        graph.add_edge(1, 2);
+
+        let dag = ConnectivityDirectedGraph::new(&graph).condensation();
+        assert_eq!(dag.num_v(), 2);
+        assert_eq!(dag.num_e(), 1);
+    }
+
+    #[test]
+    fn test_feedback_arc_set() {
+        // A 3-cycle needs exactly one edge removed to become acyclic.
+        let mut graph = DirectedGraph::new(3, 3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+
+        let fas = ConnectivityDirectedGraph::new(&graph).greedy_feedback_arc_set();
+        assert_eq!(fas.len(), 1);
+    }
+
     #[test]
     fn test_two_sat() {
         let mut graph = DirectedGraph::new(6, 8);
@@ -288,6 +813,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_dominators() {
+        // A small control-flow graph:
+        //   0 -> 1, 0 -> 2, 1 -> 3, 2 -> 3, 3 -> 4
+        // Both 1 and 2 reach 3, so 3's immediate dominator is the root 0.
+        let mut graph = DirectedGraph::new(6, 5);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+
+        let dom = Dominators::new(&graph, 0);
+        assert_eq!(dom.immediate_dominator(0), None);
+        assert_eq!(dom.immediate_dominator(1), Some(0));
+        assert_eq!(dom.immediate_dominator(3), Some(0));
+        assert_eq!(dom.immediate_dominator(4), Some(3));
+        // Vertex 5 is unreachable from the root.
+        assert_eq!(dom.immediate_dominator(5), None);
+        assert!(dom.dominators(5).is_none());
+
+        assert_eq!(dom.dominators(4).unwrap().collect::<Vec<_>>(), vec![4, 3, 0]);
+        assert_eq!(
+            dom.strict_dominators(4).unwrap().collect::<Vec<_>>(),
+            vec![3, 0]
+        );
+    }
+
     #[test]
     fn test_biconnected() {
         let mut graph = UndirectedGraph::new(3, 6);
@@ -306,4 +859,26 @@ mod test {
         //assert_eq!(bridges, vec![0, 1]);
         assert_eq!(articulation_points, vec![1]);
     }
+
+    #[test]
+    fn test_block_cut_tree() {
+        // Bowtie: two triangles (0,1,2) and (2,3,4) sharing vertex 2.
+        let mut graph = UndirectedGraph::new(5, 12);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 2);
+
+        let cg = ConnectivityUndirectedGraph::new(&graph);
+        let bct = cg.block_cut_tree();
+
+        // Two blocks plus the shared articulation vertex 2.
+        assert_eq!(bct.tree.num_v(), 3);
+        assert_eq!(bct.tree.num_e(), 2);
+
+        let shared = bct.ap_node[2].expect("vertex 2 is an articulation point");
+        assert!(bct.is_articulation[shared]);
+    }
 }